@@ -1,8 +1,17 @@
 use std::collections::HashSet;
 
+mod scc;
+mod union_find;
+mod wire;
+
+#[cfg(feature = "petgraph")]
+mod petgraph_interop;
+
 pub use rand_core;
+pub use wire::DecodeError;
 
 /// DodoPoW graph storage.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Graph(Box<[(u32, u32)]>);
 
@@ -32,19 +41,17 @@ impl Graph {
         Self(nodes.into_boxed_slice())
     }
 
-    /// Search for cycles of the graph.
+    /// Build the top/bottom transition matrices and trim them to a
+    /// fixpoint (or until `max_rounds` is reached).
     ///
-    /// - `max_depth` specifies maximal length of a potential cycle.
-    /// - `handler` accepts all the found cycles, and if `true` is returned then
-    ///   search is stopped.
-    pub fn solve(
-        &self,
-        max_depth: usize,
-        mut handler: impl FnMut(&[u32]) -> bool
-    ) -> Option<Box<[u32]>> {
+    /// Only nodes with degree >= 2 on both sides can take part in a cycle,
+    /// so removing a top node can drop a bottom node below that threshold
+    /// and vice versa. Alternating top/bottom trimming rounds until a full
+    /// round leaves the graph unchanged typically removes the overwhelming
+    /// majority of edges before any search even starts.
+    fn build_trimmed(&self, max_rounds: Option<usize>) -> (Vec<Vec<u32>>, Vec<Vec<u32>>) {
         let edges_number = self.0.len();
 
-        // Build transition matrices.
         let mut top_nodes = vec![vec![]; edges_number];
         let mut bottom_nodes = vec![vec![]; edges_number];
 
@@ -58,34 +65,117 @@ impl Graph {
             }
         }
 
-        // Prune top nodes with less than 2 edges.
-        #[allow(clippy::needless_range_loop)]
-        for top_node in 0..edges_number {
-            if top_nodes[top_node].len() < 2 {
-                for bottom_node in &top_nodes[top_node] {
-                    bottom_nodes[*bottom_node as usize].retain(|node| {
-                        node != &(top_node as u32)
-                    });
+        let mut round = 0;
+
+        loop {
+            let mut changed = false;
+
+            #[allow(clippy::needless_range_loop)]
+            for top_node in 0..edges_number {
+                if top_nodes[top_node].len() < 2 && !top_nodes[top_node].is_empty() {
+                    for bottom_node in &top_nodes[top_node] {
+                        bottom_nodes[*bottom_node as usize].retain(|node| {
+                            node != &(top_node as u32)
+                        });
+                    }
+
+                    top_nodes[top_node].clear();
+                    changed = true;
+                }
+            }
+
+            #[allow(clippy::needless_range_loop)]
+            for bottom_node in 0..edges_number {
+                if bottom_nodes[bottom_node].len() < 2 && !bottom_nodes[bottom_node].is_empty() {
+                    for top_node in &bottom_nodes[bottom_node] {
+                        top_nodes[*top_node as usize].retain(|node| {
+                            node != &(bottom_node as u32)
+                        });
+                    }
+
+                    bottom_nodes[bottom_node].clear();
+                    changed = true;
                 }
+            }
+
+            round += 1;
 
-                top_nodes[top_node].clear();
+            if !changed || max_rounds.is_some_and(|max| round >= max) {
+                break;
             }
         }
 
-        // Prune bottom nodes with less than 2 edges.
+        (top_nodes, bottom_nodes)
+    }
+
+    /// Search for cycles of the graph.
+    ///
+    /// - `max_depth` specifies maximal length of a potential cycle.
+    /// - `handler` accepts all the found cycles, and if `true` is returned then
+    ///   search is stopped.
+    ///
+    /// This trims the graph to a fixpoint before running the DFS. Use
+    /// [`Graph::solve_with_opts`] to cap the number of trimming rounds.
+    pub fn solve(
+        &self,
+        max_depth: usize,
+        handler: impl FnMut(&[u32]) -> bool
+    ) -> Option<Box<[u32]>> {
+        self.solve_with_opts(max_depth, None, handler)
+    }
+
+    /// Search for cycles of the graph, with tunable trimming effort.
+    ///
+    /// - `max_depth` specifies maximal length of a potential cycle.
+    /// - `max_rounds` caps how many top/bottom degree-<2 trimming rounds are
+    ///   performed before the DFS starts. `None` keeps trimming until a full
+    ///   round removes nothing (a fixpoint). Lower values trade search-space
+    ///   reduction for less up-front time and memory.
+    /// - `handler` accepts all the found cycles, and if `true` is returned then
+    ///   search is stopped.
+    pub fn solve_with_opts(
+        &self,
+        max_depth: usize,
+        max_rounds: Option<usize>,
+        mut handler: impl FnMut(&[u32]) -> bool
+    ) -> Option<Box<[u32]>> {
+        let edges_number = self.0.len();
+        let (top_nodes, bottom_nodes) = self.build_trimmed(max_rounds);
+
+        // Build the directed alternating view of the trimmed graph (top to
+        // bottom along stored edges, bottom to top along the reversed
+        // edges already tracked in `bottom_nodes`) and decompose it into
+        // strongly connected components. A cycle can only pass through a
+        // node that sits in a non-trivial (size > 1) component, so roots in
+        // trivial components are skipped below. Trimming keeps `top_nodes`
+        // and `bottom_nodes` mirrored, so every edge of this view has its
+        // reverse and any top node with a surviving edge is already in a
+        // non-trivial component: this skips no root that the empty
+        // adjacency check does not.
+        let mut alternating_view = vec![Vec::new(); edges_number * 2];
+
         #[allow(clippy::needless_range_loop)]
-        for bottom_node in 0..edges_number {
-            if bottom_nodes[bottom_node].len() < 2 {
-                for top_node in &bottom_nodes[bottom_node] {
-                    top_nodes[*top_node as usize].retain(|node| {
-                        node != &(bottom_node as u32)
-                    });
-                }
+        for top_node in 0..edges_number {
+            for &bottom_node in &top_nodes[top_node] {
+                alternating_view[top_node].push(edges_number as u32 + bottom_node);
+            }
+        }
 
-                bottom_nodes[bottom_node].clear();
+        #[allow(clippy::needless_range_loop)]
+        for bottom_node in 0..edges_number {
+            for &top_node in &bottom_nodes[bottom_node] {
+                alternating_view[edges_number + bottom_node].push(top_node);
             }
         }
 
+        let component_of = scc::tarjan_scc(&alternating_view);
+
+        let mut component_size = vec![0_u32; edges_number * 2];
+
+        for &component in &component_of {
+            component_size[component as usize] += 1;
+        }
+
         // Run iterative DFS over the graph.
         #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
         enum GraphSide {
@@ -98,6 +188,10 @@ impl Graph {
                 continue;
             }
 
+            if component_size[component_of[target_top_node as usize] as usize] <= 1 {
+                continue;
+            }
+
             let mut stack: Vec<(GraphSide, Box<[u32]>)> = Vec::new();
             let mut visited = HashSet::new();
 
@@ -162,6 +256,117 @@ impl Graph {
         self.solve(diff, |cycle| cycle.len() == diff)
     }
 
+    /// Search for a cycle of length `diff` using a union-find spanning
+    /// forest instead of the depth-bounded DFS behind [`Graph::solve_for`].
+    ///
+    /// The `diff` value must be odd due to the graph structure, and longer
+    /// than 3 to form a proper cycle, meaning `diff` is an odd number
+    /// starting from 5.
+    ///
+    /// After trimming the graph to a fixpoint, every surviving edge is fed
+    /// into a union-find over all `2N` nodes. As long as an edge `(u, v)`
+    /// joins two different sets, it is unioned and recorded as a tree edge
+    /// of a spanning forest. Every edge that joins two nodes already in
+    /// the same set closes a cycle: it is reconstructed by walking the
+    /// spanning-forest parent pointers from `u` and from `v` up to their
+    /// common ancestor and appending the closing edge, and the first such
+    /// cycle of length `diff` is returned. This makes cycle
+    /// existence near-linear to test, at the cost of only finding cycles
+    /// that close the very first time their endpoints meet, rather than
+    /// every possible cycle the exhaustive search could find.
+    pub fn solve_union_find(&self, diff: usize) -> Option<Box<[u32]>> {
+        if diff % 2 == 0 || diff < 5 {
+            return None;
+        }
+
+        let edges_number = self.0.len() as u32;
+        let (top_nodes, _) = self.build_trimmed(None);
+
+        let to_label = |node: u32| -> u32 {
+            if node >= edges_number {
+                node - edges_number
+            } else {
+                node
+            }
+        };
+
+        let mut union_find = union_find::UnionFind::new(edges_number as usize * 2);
+        let mut spanning_parent: Vec<u32> = (0..edges_number * 2).collect();
+
+        for top_node in 0..edges_number {
+            for &bottom_node in &top_nodes[top_node as usize] {
+                let node_v = edges_number + bottom_node;
+
+                if union_find.union(top_node, node_v) {
+                    // After trimming, every surviving bottom node has degree
+                    // >= 2, so `node_v` may already be an interior node of a
+                    // subtree grown from an earlier edge. Re-root that
+                    // subtree at `node_v` itself (reversing parent pointers
+                    // along the way) before attaching it under `top_node`,
+                    // rather than jumping straight to its current root: the
+                    // root could be on either side of the bipartition, and
+                    // attaching it directly could link two top (or two
+                    // bottom) nodes, breaking the top/bottom alternation the
+                    // cycle reconstruction below relies on.
+                    let mut node = node_v;
+                    let mut parent = spanning_parent[node as usize];
+
+                    while parent != node {
+                        let grandparent = spanning_parent[parent as usize];
+
+                        spanning_parent[parent as usize] = node;
+                        node = parent;
+                        parent = grandparent;
+                    }
+
+                    spanning_parent[node_v as usize] = top_node;
+                    continue;
+                }
+
+                // `top_node` and `node_v` are already connected, so this
+                // edge closes a cycle: walk both nodes' forest paths up to
+                // their common ancestor.
+                let mut u_path = vec![top_node];
+                let mut node = top_node;
+
+                while spanning_parent[node as usize] != node {
+                    node = spanning_parent[node as usize];
+                    u_path.push(node);
+                }
+
+                let u_ancestors: HashSet<u32> = u_path.iter().copied().collect();
+
+                let mut v_path = vec![node_v];
+                let mut node = node_v;
+
+                while !u_ancestors.contains(&node) {
+                    node = spanning_parent[node as usize];
+                    v_path.push(node);
+                }
+
+                let common_ancestor = *v_path.last().expect("v_path always has the common ancestor");
+                let split = u_path.iter().position(|&n| n == common_ancestor)
+                    .expect("common_ancestor is a member of u_path by construction");
+
+                let mut cycle = u_path[..=split].to_vec();
+
+                cycle.extend(v_path.into_iter().rev().skip(1));
+                cycle.push(top_node);
+
+                if cycle.len() % 2 == 1 && cycle.len() == diff {
+                    return Some(
+                        cycle.into_iter()
+                            .map(to_label)
+                            .collect::<Vec<_>>()
+                            .into_boxed_slice()
+                    );
+                }
+            }
+        }
+
+        None
+    }
+
     /// Verify the cycle.
     pub fn verify(&self, cycle: &[u32]) -> bool {
         let n = cycle.len();
@@ -219,3 +424,37 @@ fn test() {
         1981
     ]));
 }
+
+#[test]
+fn test_trim_removes_dangling_edges() {
+    // top0-b0 and top1-b0 form a dangling chain, not a cycle: every node
+    // has degree < 2 on at least one side, so trimming should clear the
+    // graph entirely and leave solve_with_opts nothing to search.
+    let graph = Graph(Box::new([(0, 0), (1, 0)]));
+
+    assert_eq!(graph.solve_with_opts(99, None, |_| true), None);
+}
+
+#[test]
+fn test_solve_small_cycle() {
+    // A minimal K(2,2) bipartite graph: every node has degree 2, so the
+    // whole thing survives trimming and forms a single non-trivial SCC,
+    // which solve's SCC pre-pass must not filter away.
+    let graph = Graph(Box::new([(0, 0), (1, 0), (1, 1), (0, 1)]));
+
+    assert!(graph.solve_for(5).is_some());
+}
+
+#[test]
+fn test_solve_union_find() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+
+    let graph = Graph::new(&mut rng, 16);
+
+    let cycle = graph.solve_union_find(9).expect("a length-9 cycle is known to exist");
+
+    assert_eq!(cycle.len(), 9);
+    assert!(graph.verify(&cycle));
+}
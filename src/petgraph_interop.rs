@@ -0,0 +1,243 @@
+//! Optional interop with [`petgraph`], enabled via the `petgraph` feature.
+//!
+//! Top and bottom nodes share the same `0..edges_number` id range inside
+//! [`Graph`], so every conversion here offsets bottom node ids by
+//! `edges_number` to give petgraph a single disjoint id space, mirroring the
+//! alternating view built internally by [`Graph::solve`].
+
+use std::collections::HashSet;
+
+use petgraph::algo::simple_paths::all_simple_paths;
+use petgraph::graphmap::UnGraphMap;
+
+use crate::Graph;
+
+impl From<&Graph> for UnGraphMap<u32, ()> {
+    /// Convert the graph into a petgraph undirected graph map, so callers
+    /// who already depend on petgraph can run its SCC, matching, and
+    /// traversal algorithms over a DodoPoW graph.
+    fn from(graph: &Graph) -> Self {
+        let edges_number = graph.0.len() as u32;
+
+        let mut map = UnGraphMap::with_capacity(
+            edges_number as usize * 2,
+            edges_number as usize
+        );
+
+        for &(top_node, bottom_node) in graph.0.iter() {
+            map.add_edge(top_node, edges_number + bottom_node, ());
+        }
+
+        map
+    }
+}
+
+impl Graph {
+    /// Rebuild a graph from an edge list using the same offset node
+    /// numbering produced by `From<&Graph> for UnGraphMap<u32, ()>`, e.g.
+    /// after running petgraph algorithms over the converted graph and
+    /// wanting to round-trip the result back into a `Graph`.
+    ///
+    /// - `edges_number` must be the `N` the edges were offset against (the
+    ///   same value as `self.0.len()` of the graph that was converted).
+    ///
+    /// Panics if the resulting edge count is not a power of two: the rest
+    /// of the crate (in particular [`Graph::to_bytes`]) assumes every
+    /// `Graph` has `2^n` edges, but `UnGraphMap` dedups edges while
+    /// `Graph` intentionally keeps duplicates, so an edge list coming back
+    /// from petgraph after such a round-trip can easily violate that
+    /// invariant.
+    ///
+    /// Also panics if an edge doesn't have exactly one endpoint `>= edges_number`
+    /// (so it can't be un-offset back onto a single `0..edges_number` side) or
+    /// if un-offsetting it would still leave a node `>= edges_number`, since
+    /// either would otherwise panic later, deep inside [`Graph::solve`] and
+    /// its siblings, instead of at the point the bad id was introduced.
+    pub fn from_petgraph_edges(
+        edges_number: usize,
+        edges: impl IntoIterator<Item = (u32, u32)>
+    ) -> Self {
+        let offset = edges_number as u32;
+
+        let nodes = edges
+            .into_iter()
+            .map(|(a, b)| {
+                let (top, bottom) = if a >= offset {
+                    (b, a - offset)
+                } else {
+                    let bottom = b.checked_sub(offset).unwrap_or_else(|| {
+                        panic!(
+                            "edge ({a}, {b}) must have exactly one endpoint >= edges_number ({edges_number})"
+                        )
+                    });
+
+                    (a, bottom)
+                };
+
+                assert!(
+                    top < offset && bottom < offset,
+                    "node out of range for edges_number {edges_number}: top={top}, bottom={bottom}"
+                );
+
+                (top, bottom)
+            })
+            .collect::<Vec<_>>();
+
+        assert!(
+            nodes.len().is_power_of_two(),
+            "edge list must have a power-of-two length, got {}",
+            nodes.len()
+        );
+
+        Self(nodes.into_boxed_slice())
+    }
+
+    /// Enumerate every distinct cycle up to `max_depth`, rather than only
+    /// the first one [`Graph::solve`] finds.
+    ///
+    /// Trims the graph to a fixpoint first (see [`Graph::solve_with_opts`]),
+    /// then for every surviving edge reuses petgraph's
+    /// `simple_paths::all_simple_paths` to find every simple path that
+    /// closes it back into a cycle, so miners can evaluate multiple
+    /// candidate cycles per challenge instead of only one.
+    pub fn solve_all_simple(&self, max_depth: usize) -> impl Iterator<Item = Box<[u32]>> {
+        let edges_number = self.0.len() as u32;
+        let (top_nodes, _) = self.build_trimmed(None);
+
+        let mut graph_map = UnGraphMap::with_capacity(edges_number as usize * 2, edges_number as usize);
+
+        for (top_node, bottom_nodes) in top_nodes.iter().enumerate() {
+            for &bottom_node in bottom_nodes {
+                graph_map.add_edge(top_node as u32, edges_number + bottom_node, ());
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut cycles = Vec::new();
+
+        for top_node in 0..edges_number {
+            for &first_bottom in &top_nodes[top_node as usize] {
+                let paths = all_simple_paths::<Vec<_>, _>(
+                    &graph_map,
+                    edges_number + first_bottom,
+                    top_node,
+                    1,
+                    Some(max_depth.saturating_sub(3))
+                );
+
+                for path in paths {
+                    let mut cycle = Vec::with_capacity(path.len() + 1);
+
+                    cycle.push(top_node);
+
+                    for node in path {
+                        cycle.push(if node >= edges_number {
+                            node - edges_number
+                        } else {
+                            node
+                        });
+                    }
+
+                    // Cycles alternate sides and must be odd-length (see
+                    // `Graph::verify`); a shorter one is not a proper cycle.
+                    if cycle.len() % 2 == 0 || cycle.len() <= 3 {
+                        continue;
+                    }
+
+                    // The same cycle is found once per top node on it and
+                    // once per direction, so key it by its smallest
+                    // top-first rotation in either direction.
+                    let ring = &cycle[..cycle.len() - 1];
+                    let len = ring.len();
+
+                    let key = (0..len).step_by(2)
+                        .flat_map(|start| [true, false].map(|forward| {
+                            (0..=len).map(|i| {
+                                let pos = if forward { start + i } else { start + len - i };
+
+                                ring[pos % len]
+                            }).collect::<Vec<_>>()
+                        }))
+                        .min()
+                        .expect("a cycle has at least one top node");
+
+                    if seen.insert(key) {
+                        cycles.push(cycle.into_boxed_slice());
+                    }
+                }
+            }
+        }
+
+        cycles.into_iter()
+    }
+}
+
+#[test]
+fn test_solve_all_simple() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+
+    let graph = Graph::new(&mut rng, 16);
+
+    let known_cycle: [u32; 9] = [
+        1981,
+        19107,
+        3084,
+        24653,
+        6267,
+        46608,
+        34728,
+        11923,
+        1981
+    ];
+
+    let mut reversed = known_cycle;
+
+    reversed.reverse();
+
+    let found = graph.solve_all_simple(9).any(|cycle| {
+        &*cycle == known_cycle.as_slice() || &*cycle == reversed.as_slice()
+    });
+
+    assert!(found, "solve_all_simple should yield the known length-9 cycle");
+}
+
+#[test]
+#[should_panic(expected = "power-of-two")]
+fn test_from_petgraph_edges_rejects_deduped_edges() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+
+    // With n = 8 and this seed, two of the 256 random top/bottom pairs
+    // collide, so converting to `UnGraphMap` (which dedups edges) and back
+    // leaves 255 edges, not a power-of-two count.
+    let graph = Graph::new(&mut rng, 8);
+    let edges_number = 1_usize << 8;
+
+    let map = UnGraphMap::<u32, ()>::from(&graph);
+    let edges = map.all_edges().map(|(a, b, _)| (a, b)).collect::<Vec<_>>();
+
+    Graph::from_petgraph_edges(edges_number, edges);
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn test_from_petgraph_edges_rejects_out_of_range_top() {
+    // edges_number = 2, so valid node ids are 0..2 and offset bottom ids
+    // are 2..4; a top id of 5 is out of range on either side and must be
+    // rejected rather than silently accepted into a Graph that panics the
+    // first time it's solved.
+    Graph::from_petgraph_edges(2, [(5, 2), (0, 3)]);
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn test_from_petgraph_edges_rejects_out_of_range_bottom() {
+    // edges_number = 2: node 0 is a valid top and node 10 is >= offset so
+    // it's treated as a bottom id, but 10 - offset = 8 is still outside
+    // 0..2. This must be rejected rather than silently stored as an
+    // out-of-range bottom node.
+    Graph::from_petgraph_edges(2, [(0, 10), (1, 3)]);
+}
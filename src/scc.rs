@@ -0,0 +1,86 @@
+//! Iterative Tarjan strongly-connected-components decomposition.
+//!
+//! Implemented with an explicit stack (rather than recursion) so it does not
+//! blow the call stack on the dense alternating views built for large `n`.
+
+/// Decompose a directed graph given as an adjacency list into strongly
+/// connected components.
+///
+/// Returns a component id per node. Two nodes share a component id if and
+/// only if each is reachable from the other.
+pub(crate) fn tarjan_scc(adjacency: &[Vec<u32>]) -> Box<[u32]> {
+    let nodes_number = adjacency.len();
+
+    let mut index_of = vec![u32::MAX; nodes_number];
+    let mut low_link = vec![0; nodes_number];
+    let mut on_stack = vec![false; nodes_number];
+    let mut comp_of = vec![u32::MAX; nodes_number];
+
+    let mut node_stack = Vec::new();
+    let mut next_index = 0;
+    let mut next_comp = 0;
+
+    // Explicit call stack standing in for Tarjan's recursion: each frame is
+    // a node together with the index of the next child edge to follow.
+    let mut call_stack: Vec<(u32, usize)> = Vec::new();
+
+    for start in 0..nodes_number as u32 {
+        if index_of[start as usize] != u32::MAX {
+            continue;
+        }
+
+        call_stack.push((start, 0));
+
+        while let Some(&mut (node, ref mut child_pos)) = call_stack.last_mut() {
+            let node_usize = node as usize;
+
+            if *child_pos == 0 {
+                index_of[node_usize] = next_index;
+                low_link[node_usize] = next_index;
+                next_index += 1;
+
+                node_stack.push(node);
+                on_stack[node_usize] = true;
+            }
+
+            if *child_pos < adjacency[node_usize].len() {
+                let child = adjacency[node_usize][*child_pos];
+
+                *child_pos += 1;
+
+                if index_of[child as usize] == u32::MAX {
+                    call_stack.push((child, 0));
+                } else if on_stack[child as usize] {
+                    low_link[node_usize] = low_link[node_usize].min(index_of[child as usize]);
+                }
+            }
+
+            else {
+                call_stack.pop();
+
+                if let Some((parent, _)) = call_stack.last() {
+                    let parent_usize = *parent as usize;
+
+                    low_link[parent_usize] = low_link[parent_usize].min(low_link[node_usize]);
+                }
+
+                if low_link[node_usize] == index_of[node_usize] {
+                    loop {
+                        let member = node_stack.pop().expect("scc stack must not be empty");
+
+                        on_stack[member as usize] = false;
+                        comp_of[member as usize] = next_comp;
+
+                        if member == node {
+                            break;
+                        }
+                    }
+
+                    next_comp += 1;
+                }
+            }
+        }
+    }
+
+    comp_of.into_boxed_slice()
+}
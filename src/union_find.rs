@@ -0,0 +1,53 @@
+//! Minimal union-find, modeled on the `UnionFind` used by petgraph's `algo`
+//! module, used to test cycle existence in near-linear time.
+
+use std::cmp::Ordering;
+
+pub(crate) struct UnionFind {
+    parent: Box<[u32]>,
+    rank: Box<[u8]>
+}
+
+impl UnionFind {
+    pub(crate) fn new(nodes_number: usize) -> Self {
+        Self {
+            parent: (0..nodes_number as u32).collect(),
+            rank: vec![0; nodes_number].into_boxed_slice()
+        }
+    }
+
+    pub(crate) fn find(&mut self, mut node: u32) -> u32 {
+        while self.parent[node as usize] != node {
+            let grandparent = self.parent[self.parent[node as usize] as usize];
+
+            self.parent[node as usize] = grandparent;
+            node = grandparent;
+        }
+
+        node
+    }
+
+    /// Union the sets containing `a` and `b`. Returns `true` if they were
+    /// in different sets (and have now been merged), or `false` if they
+    /// were already in the same set.
+    pub(crate) fn union(&mut self, a: u32, b: u32) -> bool {
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+
+        if root_a == root_b {
+            return false;
+        }
+
+        match self.rank[root_a as usize].cmp(&self.rank[root_b as usize]) {
+            Ordering::Less => self.parent[root_a as usize] = root_b,
+            Ordering::Greater => self.parent[root_b as usize] = root_a,
+
+            Ordering::Equal => {
+                self.parent[root_b as usize] = root_a;
+                self.rank[root_a as usize] += 1;
+            }
+        }
+
+        true
+    }
+}
@@ -0,0 +1,216 @@
+//! Compact binary wire format for shipping challenges and solutions between
+//! a prover and a verifier.
+
+use std::fmt;
+
+use crate::Graph;
+
+/// Error produced by [`Graph::from_bytes`] or [`Graph::decode_cycle`] when
+/// the input cannot be a valid wire-format value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input is shorter than the fixed-size header it must start with.
+    TooShort,
+
+    /// The encoded `n` exceeds 32, the largest `n` accepted by [`Graph::new`].
+    NTooLarge(u8),
+
+    /// The remaining byte count does not match the length implied by the
+    /// header, so the input is rejected rather than trusting it enough to
+    /// allocate that much memory.
+    InvalidLength { expected: usize, actual: usize },
+
+    /// A decoded `(top_node, bottom_node)` pair has a node id outside
+    /// `0..edges_number`, which would otherwise panic the first time the
+    /// resulting `Graph` is passed to [`Graph::solve`] or a sibling solver.
+    NodeOutOfRange { node: u32, edges_number: u32 }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort => write!(f, "input is too short to contain a header"),
+            Self::NTooLarge(n) => write!(f, "n = {n} exceeds the maximum of 32"),
+
+            Self::InvalidLength { expected, actual } => write!(
+                f,
+                "expected {expected} bytes of payload, got {actual}"
+            ),
+
+            Self::NodeOutOfRange { node, edges_number } => write!(
+                f,
+                "node {node} is out of range for a graph with {edges_number} edges"
+            )
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl Graph {
+    /// Encode the graph as `n` followed by its `2^n` edges packed as
+    /// little-endian `u32` pairs.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let n = self.0.len().trailing_zeros() as u8;
+
+        let mut bytes = Vec::with_capacity(1 + self.0.len() * 8);
+
+        bytes.push(n);
+
+        for &(top_node, bottom_node) in self.0.iter() {
+            bytes.extend_from_slice(&top_node.to_le_bytes());
+            bytes.extend_from_slice(&bottom_node.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Decode a graph previously encoded with [`Graph::to_bytes`].
+    ///
+    /// Rejects an `n` greater than 32, a payload whose length does not
+    /// match the `2^n` edges implied by the header, and any node id
+    /// outside `0..2^n`, so a malicious peer cannot trigger an
+    /// over-allocation by lying about the header, nor an out-of-bounds
+    /// panic in [`Graph::solve`] and its siblings by lying about an edge.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let &[n, ref payload @ ..] = bytes else {
+            return Err(DecodeError::TooShort);
+        };
+
+        if n > 32 {
+            return Err(DecodeError::NTooLarge(n));
+        }
+
+        let edges_number = 1_usize << n;
+        let expected = edges_number * 8;
+
+        if payload.len() != expected {
+            return Err(DecodeError::InvalidLength {
+                expected,
+                actual: payload.len()
+            });
+        }
+
+        let mut nodes = Vec::with_capacity(edges_number);
+
+        for chunk in payload.chunks_exact(8) {
+            let top_node = u32::from_le_bytes(chunk[0..4].try_into().unwrap());
+            let bottom_node = u32::from_le_bytes(chunk[4..8].try_into().unwrap());
+
+            for node in [top_node, bottom_node] {
+                if node as usize >= edges_number {
+                    return Err(DecodeError::NodeOutOfRange {
+                        node,
+                        edges_number: edges_number as u32
+                    });
+                }
+            }
+
+            nodes.push((top_node, bottom_node));
+        }
+
+        Ok(Self(nodes.into_boxed_slice()))
+    }
+
+    /// Encode a solved cycle (as returned by [`Graph::solve`] and friends)
+    /// as its length followed by its nodes, all little-endian `u32`s.
+    pub fn encode_cycle(cycle: &[u32]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + cycle.len() * 4);
+
+        bytes.extend_from_slice(&(cycle.len() as u32).to_le_bytes());
+
+        for node in cycle {
+            bytes.extend_from_slice(&node.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Decode a cycle previously encoded with [`Graph::encode_cycle`].
+    ///
+    /// Rejects a payload whose length does not match the node count implied
+    /// by the header, so a malicious peer cannot trigger an over-allocation
+    /// by lying about it.
+    pub fn decode_cycle(bytes: &[u8]) -> Result<Box<[u32]>, DecodeError> {
+        if bytes.len() < 4 {
+            return Err(DecodeError::TooShort);
+        }
+
+        let (header, payload) = bytes.split_at(4);
+
+        let nodes_number = u32::from_le_bytes(header.try_into().unwrap()) as usize;
+        let expected = nodes_number * 4;
+
+        if payload.len() != expected {
+            return Err(DecodeError::InvalidLength {
+                expected,
+                actual: payload.len()
+            });
+        }
+
+        let cycle = payload
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect::<Vec<_>>();
+
+        Ok(cycle.into_boxed_slice())
+    }
+}
+
+#[test]
+fn test_wire_round_trip() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+
+    let graph = Graph::new(&mut rng, 16);
+
+    let bytes = graph.to_bytes();
+    let decoded = Graph::from_bytes(&bytes).expect("encoded graph must decode");
+
+    assert_eq!(graph, decoded);
+
+    let cycle: [u32; 9] = [
+        1981,
+        19107,
+        3084,
+        24653,
+        6267,
+        46608,
+        34728,
+        11923,
+        1981
+    ];
+
+    let cycle_bytes = Graph::encode_cycle(&cycle);
+    let decoded_cycle = Graph::decode_cycle(&cycle_bytes).expect("encoded cycle must decode");
+
+    assert_eq!(&*decoded_cycle, &cycle);
+}
+
+#[test]
+fn test_from_bytes_rejects_bad_input() {
+    assert_eq!(Graph::from_bytes(&[]), Err(DecodeError::TooShort));
+    assert_eq!(Graph::from_bytes(&[33]), Err(DecodeError::NTooLarge(33)));
+
+    assert_eq!(
+        Graph::from_bytes(&[1, 0, 0]),
+        Err(DecodeError::InvalidLength { expected: 16, actual: 2 })
+    );
+}
+
+#[test]
+fn test_from_bytes_rejects_out_of_range_node() {
+    // n = 0 means edges_number = 1, so node id 0 is the only valid one;
+    // u32::MAX as the top_node must be rejected rather than decoded into a
+    // Graph that panics the first time it's solved.
+    let mut bytes = vec![0];
+
+    bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+    bytes.extend_from_slice(&0_u32.to_le_bytes());
+
+    assert_eq!(
+        Graph::from_bytes(&bytes),
+        Err(DecodeError::NodeOutOfRange { node: u32::MAX, edges_number: 1 })
+    );
+}